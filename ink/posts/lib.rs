@@ -6,6 +6,7 @@ mod posts {
     type PostId = u32;
 
     use ink_prelude::{
+        boxed::Box,
         string::{String, ToString},
         vec::Vec,
     };
@@ -28,6 +29,7 @@ mod posts {
     pub enum PostType {
         RegularPost,
         Comment { parent_id: u32 },
+        Boost { original_id: PostId },
     }
 
     impl Default for PostType {
@@ -54,6 +56,8 @@ mod posts {
         comments_id: Vec<u32>,
         likes: u32,
         dislikes: u32,
+        boosts: u32,
+        media_uri: Option<String>,
     }
 
     impl Default for PostItem {
@@ -67,6 +71,8 @@ mod posts {
                 comments_id: Vec::new(),
                 likes: 0,
                 dislikes: 0,
+                boosts: 0,
+                media_uri: None,
             }
         }
     }
@@ -78,6 +84,9 @@ mod posts {
                 ReactionType::Dislike => self.dislikes = self.dislikes.saturating_add(1),
             };
         }
+        pub fn add_boost(&mut self) {
+            self.boosts = self.boosts.saturating_add(1);
+        }
         pub fn remove_reaction(&mut self, reaction: ReactionType) {
             match reaction {
                 ReactionType::Like => self.likes = self.likes.saturating_sub(1),
@@ -89,6 +98,39 @@ mod posts {
         }
     }
 
+    #[derive(Debug, Clone, Encode, Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum TimelineQuery {
+        And(Box<TimelineQuery>, Box<TimelineQuery>),
+        Or(Box<TimelineQuery>, Box<TimelineQuery>),
+        Not(Box<TimelineQuery>),
+        Author(AccountId),
+        IsComment,
+        IsRegular,
+        Keyword(String),
+        MinLikes(u32),
+        MaxDislikes(u32),
+    }
+
+    impl TimelineQuery {
+        fn matches(&self, post: &PostItem) -> bool {
+            match self {
+                TimelineQuery::And(lhs, rhs) => lhs.matches(post) && rhs.matches(post),
+                TimelineQuery::Or(lhs, rhs) => lhs.matches(post) || rhs.matches(post),
+                TimelineQuery::Not(inner) => !inner.matches(post),
+                TimelineQuery::Author(account) => post.created.account == *account,
+                TimelineQuery::IsComment => matches!(post.post_type, PostType::Comment { .. }),
+                TimelineQuery::IsRegular => matches!(post.post_type, PostType::RegularPost),
+                TimelineQuery::Keyword(keyword) => post
+                    .content
+                    .to_lowercase()
+                    .contains(&keyword.to_lowercase()),
+                TimelineQuery::MinLikes(min) => post.likes >= *min,
+                TimelineQuery::MaxDislikes(max) => post.dislikes <= *max,
+            }
+        }
+    }
+
     #[ink(event)]
     pub struct PostCreated {
         who: AccountId,
@@ -113,6 +155,33 @@ mod posts {
         reaction: ReactionType,
     }
 
+    #[ink(event)]
+    pub struct ListUpdated {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        list_id: u32,
+    }
+
+    #[ink(event)]
+    pub struct PostBoosted {
+        #[ink(topic)]
+        who: AccountId,
+        boost_post_id: PostId,
+        #[ink(topic)]
+        original_id: PostId,
+    }
+
+    #[ink(event)]
+    pub struct MediaAttached {
+        #[ink(topic)]
+        post_id: PostId,
+        media_uri: String,
+    }
+
+    /// upper bound on the length of a `media_uri`, to keep the on-chain reference small
+    const MAX_MEDIA_URI_LEN: usize = 256;
+
     #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
     pub enum Error {
@@ -121,6 +190,14 @@ mod posts {
         InvalidParentId,
         SameReaction,
         NoReaction,
+        InvalidListId,
+        NotListOwner,
+        InvalidOriginalId,
+        AlreadyBoosted,
+        NotAdmin,
+        AccountBlocked,
+        ContentBlocked,
+        InvalidMediaUri,
     }
 
     #[ink(storage)]
@@ -129,12 +206,20 @@ mod posts {
         count: u32,
         posts: Mapping<u32, PostItem>,
         reactions: Mapping<(u32, AccountId), ReactionType>,
+        lists: Mapping<(AccountId, u32), Vec<AccountId>>,
+        list_count: Mapping<AccountId, u32>,
+        boosted_by: Mapping<(PostId, AccountId), ()>,
+        admin: AccountId,
+        blocked_accounts: Mapping<AccountId, ()>,
+        blocked_terms: Vec<String>,
     }
 
     impl Posts {
         #[ink(constructor)]
         pub fn new() -> Self {
-            ink_lang::utils::initialize_contract(|_| {})
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                contract.admin = Self::env().caller();
+            })
         }
 
         /// Constructor that initializes the `bool` value to `false`.
@@ -142,7 +227,59 @@ mod posts {
         /// Constructors can delegate to other constructors.
         #[ink(constructor)]
         pub fn default() -> Self {
-            ink_lang::utils::initialize_contract(|_| {})
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                contract.admin = Self::env().caller();
+            })
+        }
+
+        /// block an account from creating posts
+        /// only callable by the admin
+        #[ink(message)]
+        pub fn block_account(&mut self, account: AccountId) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.blocked_accounts.insert(account, &());
+            Ok(())
+        }
+
+        /// unblock a previously blocked account
+        /// only callable by the admin
+        #[ink(message)]
+        pub fn unblock_account(&mut self, account: AccountId) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.blocked_accounts.remove(account);
+            Ok(())
+        }
+
+        /// add a term to the content blocklist
+        /// only callable by the admin
+        #[ink(message)]
+        pub fn add_blocked_term(&mut self, term: String) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.blocked_terms.push(term);
+            Ok(())
+        }
+
+        /// remove a term from the content blocklist
+        /// only callable by the admin
+        #[ink(message)]
+        pub fn remove_blocked_term(&mut self, term: String) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.blocked_terms.retain(|blocked| blocked != &term);
+            Ok(())
+        }
+
+        fn ensure_admin(&self) -> Result<(), Error> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NotAdmin)
+            }
+            Ok(())
+        }
+
+        fn is_content_blocked(&self, content: &str) -> bool {
+            let content = content.to_lowercase();
+            self.blocked_terms
+                .iter()
+                .any(|term| content.contains(&term.to_lowercase()))
         }
 
         /// Create a new post
@@ -150,13 +287,38 @@ mod posts {
         /// content: content of the post (should not be empty)
         #[ink(message)]
         pub fn create_post(&mut self, post_type: PostType, content: String) -> Result<(), Error> {
+            self.create_post_with_media(post_type, content, None)
+        }
+
+        /// Create a new post with an optional off-chain media reference (IPFS CID, S3-style URI, ...)
+        /// post_type: Regular Post / Comment / Boost
+        /// content: content of the post (should not be empty)
+        /// media_uri: optional locator for off-chain media attached to the post
+        #[ink(message)]
+        pub fn create_post_with_media(
+            &mut self,
+            post_type: PostType,
+            content: String,
+            media_uri: Option<String>,
+        ) -> Result<(), Error> {
             if content.is_empty() {
                 return Err(Error::ContentEmpty)
             }
+            if let Some(uri) = &media_uri {
+                if uri.is_empty() || uri.len() > MAX_MEDIA_URI_LEN {
+                    return Err(Error::InvalidMediaUri)
+                }
+            }
             let creator = Self::env().caller();
+            if self.blocked_accounts.contains(creator) {
+                return Err(Error::AccountBlocked)
+            }
+            if self.is_content_blocked(&content) {
+                return Err(Error::ContentBlocked)
+            }
             let post_id = self.count + 1;
 
-            let comment_check = match post_type {
+            let type_check = match post_type {
                 PostType::Comment { parent_id } => match &mut self.posts.get(parent_id) {
                     None => Err(Error::InvalidParentId),
                     Some(post) => {
@@ -165,11 +327,29 @@ mod posts {
                         Ok(())
                     },
                 },
+                PostType::Boost { original_id } => match &mut self.posts.get(original_id) {
+                    None => Err(Error::InvalidOriginalId),
+                    Some(original) => {
+                        if self.boosted_by.contains((original_id, creator)) {
+                            Err(Error::AlreadyBoosted)
+                        } else {
+                            original.add_boost();
+                            self.posts.insert(original_id, original);
+                            self.boosted_by.insert((original_id, creator), &());
+                            Ok(())
+                        }
+                    },
+                },
                 _ => Ok(()),
             };
 
-            match comment_check {
+            match type_check {
                 Ok(_) => {
+                    let boosted_original_id = match post_type {
+                        PostType::Boost { original_id } => Some(original_id),
+                        _ => None,
+                    };
+
                     self.posts.insert(
                         post_id,
                         &PostItem {
@@ -181,6 +361,7 @@ mod posts {
                             },
                             post_type,
                             content,
+                            media_uri: media_uri.clone(),
                             ..Default::default()
                         },
                     );
@@ -188,9 +369,21 @@ mod posts {
 
                     self.env().emit_event(PostCreated { who: creator, post_id });
 
+                    if let Some(original_id) = boosted_original_id {
+                        self.env().emit_event(PostBoosted {
+                            who: creator,
+                            boost_post_id: post_id,
+                            original_id,
+                        });
+                    }
+
+                    if let Some(media_uri) = media_uri {
+                        self.env().emit_event(MediaAttached { post_id, media_uri });
+                    }
+
                     Ok(())
                 },
-                _ => comment_check,
+                _ => type_check,
             }
         }
 
@@ -211,6 +404,97 @@ mod posts {
             self.count
         }
 
+        /// query posts matching a `TimelineQuery` predicate tree
+        /// offset/limit: paginate the matching ids so the return buffer stays bounded
+        #[ink(message)]
+        pub fn query_posts(&self, query: TimelineQuery, offset: u32, limit: u32) -> Vec<PostItem> {
+            let mut matched = Vec::new();
+            for post_id in 1..=self.count {
+                if let Some(post) = self.posts.get(post_id) {
+                    if query.matches(&post) {
+                        matched.push(post);
+                    }
+                }
+            }
+            matched
+                .into_iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .collect()
+        }
+
+        /// create a new, empty list owned by the caller
+        /// returns the new list's id
+        #[ink(message)]
+        pub fn create_list(&mut self) -> u32 {
+            let owner = Self::env().caller();
+            let list_id = self.list_count.get(owner).unwrap_or(0);
+            self.lists.insert((owner, list_id), &Vec::new());
+            self.list_count.insert(owner, &(list_id + 1));
+            list_id
+        }
+
+        /// add an account to one of the caller's own lists
+        #[ink(message)]
+        pub fn add_to_list(&mut self, list_id: u32, account: AccountId) -> Result<(), Error> {
+            let owner = Self::env().caller();
+            let mut members = self.get_owned_list(owner, list_id)?;
+            if !members.contains(&account) {
+                members.push(account);
+            }
+            self.lists.insert((owner, list_id), &members);
+            self.env().emit_event(ListUpdated { owner, list_id });
+            Ok(())
+        }
+
+        /// remove an account from one of the caller's own lists
+        #[ink(message)]
+        pub fn remove_from_list(&mut self, list_id: u32, account: AccountId) -> Result<(), Error> {
+            let owner = Self::env().caller();
+            let mut members = self.get_owned_list(owner, list_id)?;
+            members.retain(|member| member != &account);
+            self.lists.insert((owner, list_id), &members);
+            self.env().emit_event(ListUpdated { owner, list_id });
+            Ok(())
+        }
+
+        /// fetch the posts authored by members of `owner`'s list `list_id`
+        /// offset/limit: paginate the matching ids so the return buffer stays bounded
+        #[ink(message)]
+        pub fn get_list_posts(
+            &self,
+            owner: AccountId,
+            list_id: u32,
+            offset: u32,
+            limit: u32,
+        ) -> Result<Vec<PostItem>, Error> {
+            if Self::env().caller() != owner {
+                return Err(Error::NotListOwner)
+            }
+            let members = self.get_owned_list(owner, list_id)?;
+            let mut matched = Vec::new();
+            for post_id in 1..=self.count {
+                if let Some(post) = self.posts.get(post_id) {
+                    if members.contains(&post.created.account) {
+                        matched.push(post);
+                    }
+                }
+            }
+            Ok(matched
+                .into_iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .collect())
+        }
+
+        /// load the members of `owner`'s list `list_id`, checking that it exists
+        fn get_owned_list(&self, owner: AccountId, list_id: u32) -> Result<Vec<AccountId>, Error> {
+            if list_id >= self.list_count.get(owner).unwrap_or(0) {
+                return Err(Error::InvalidListId)
+            }
+            Ok(self.lists.get((owner, list_id)).unwrap_or_default())
+        }
+
         /// add reaction for a post
         /// post_id: id of the post to react
         /// reaction: like or dislike
@@ -286,7 +570,8 @@ mod posts {
     }
     #[cfg(test)]
     mod tests {
-        use super::{Error, PostType, Posts, ReactionType};
+        use super::{Error, PostType, Posts, ReactionType, TimelineQuery};
+        use ink_env::test::default_accounts;
 
         /// Imports `ink_lang` so we can use `#[ink::test]`.
         use ink_lang as ink;
@@ -359,5 +644,153 @@ mod posts {
             assert_eq!(contract.get_post_by_id(1).unwrap().likes, 0);
             assert_eq!(contract.get_post_by_id(1).unwrap().dislikes, 0);
         }
+
+        #[ink::test]
+        fn test_query_posts_keyword() {
+            let mut contract = Posts::default();
+            assert!(contract.create_post(PostType::RegularPost, "hello world".to_string()).is_ok());
+            assert!(contract.create_post(PostType::RegularPost, "goodbye".to_string()).is_ok());
+            let result = contract.query_posts(TimelineQuery::Keyword("HELLO".to_string()), 0, 10);
+            assert_eq!(result.len(), 1);
+            assert_eq!(result[0].content, "hello world".to_string());
+        }
+
+        #[ink::test]
+        fn test_query_posts_pagination() {
+            let mut contract = Posts::default();
+            assert!(contract.create_post(PostType::RegularPost, "POST 1".to_string()).is_ok());
+            assert!(contract.create_post(PostType::RegularPost, "POST 2".to_string()).is_ok());
+            assert!(contract.create_post(PostType::RegularPost, "POST 3".to_string()).is_ok());
+            let result = contract.query_posts(TimelineQuery::IsRegular, 1, 1);
+            assert_eq!(result.len(), 1);
+            assert_eq!(result[0].content, "POST 2".to_string());
+        }
+
+        #[ink::test]
+        fn test_list_posts() {
+            let accounts = default_accounts::<ink_env::DefaultEnvironment>();
+            let mut contract = Posts::default();
+            assert!(contract.create_post(PostType::RegularPost, "POST 1".to_string()).is_ok());
+
+            let list_id = contract.create_list();
+            assert!(contract.add_to_list(list_id, accounts.alice).is_ok());
+            let result = contract.get_list_posts(accounts.alice, list_id, 0, 10).unwrap();
+            assert_eq!(result.len(), 1);
+
+            assert!(contract.remove_from_list(list_id, accounts.alice).is_ok());
+            let result = contract.get_list_posts(accounts.alice, list_id, 0, 10).unwrap();
+            assert_eq!(result.len(), 0);
+        }
+
+        #[ink::test]
+        fn test_list_invalid_id() {
+            let accounts = default_accounts::<ink_env::DefaultEnvironment>();
+            let mut contract = Posts::default();
+            assert_eq!(
+                contract.add_to_list(0, accounts.alice),
+                Err(Error::InvalidListId)
+            );
+        }
+
+        #[ink::test]
+        fn test_boost_post_ok() {
+            let mut contract = Posts::default();
+            assert!(contract.create_post(PostType::RegularPost, "POST 1".to_string()).is_ok());
+            assert!(contract
+                .create_post(PostType::Boost { original_id: 1 }, "BOOST".to_string())
+                .is_ok());
+            assert_eq!(contract.get_post_by_id(1).unwrap().boosts, 1);
+        }
+
+        #[ink::test]
+        fn test_boost_post_invalid_original() {
+            let mut contract = Posts::default();
+            assert_eq!(
+                contract.create_post(PostType::Boost { original_id: 1 }, "BOOST".to_string()),
+                Err(Error::InvalidOriginalId)
+            );
+        }
+
+        #[ink::test]
+        fn test_boost_post_already_boosted() {
+            let mut contract = Posts::default();
+            assert!(contract.create_post(PostType::RegularPost, "POST 1".to_string()).is_ok());
+            assert!(contract
+                .create_post(PostType::Boost { original_id: 1 }, "BOOST".to_string())
+                .is_ok());
+            assert_eq!(
+                contract.create_post(PostType::Boost { original_id: 1 }, "BOOST AGAIN".to_string()),
+                Err(Error::AlreadyBoosted)
+            );
+        }
+
+        #[ink::test]
+        fn test_block_account() {
+            let accounts = default_accounts::<ink_env::DefaultEnvironment>();
+            let mut contract = Posts::default();
+            assert!(contract.block_account(accounts.bob).is_ok());
+
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                contract.create_post(PostType::RegularPost, "POST".to_string()),
+                Err(Error::AccountBlocked)
+            );
+        }
+
+        #[ink::test]
+        fn test_block_account_requires_admin() {
+            let accounts = default_accounts::<ink_env::DefaultEnvironment>();
+            let mut contract = Posts::default();
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.block_account(accounts.charlie), Err(Error::NotAdmin));
+        }
+
+        #[ink::test]
+        fn test_blocked_term() {
+            let mut contract = Posts::default();
+            assert!(contract.add_blocked_term("spam".to_string()).is_ok());
+            assert_eq!(
+                contract.create_post(PostType::RegularPost, "totally not SPAM".to_string()),
+                Err(Error::ContentBlocked)
+            );
+            assert!(contract.remove_blocked_term("spam".to_string()).is_ok());
+            assert!(contract
+                .create_post(PostType::RegularPost, "totally not SPAM".to_string())
+                .is_ok());
+        }
+
+        #[ink::test]
+        fn test_create_post_with_media() {
+            let mut contract = Posts::default();
+            assert!(contract
+                .create_post_with_media(
+                    PostType::RegularPost,
+                    "POST 1".to_string(),
+                    Some("ipfs://Qm123".to_string()),
+                )
+                .is_ok());
+            let post = contract.get_post_by_id(1).unwrap();
+            assert_eq!(post.media_uri, Some("ipfs://Qm123".to_string()));
+        }
+
+        #[ink::test]
+        fn test_create_post_without_media() {
+            let mut contract = Posts::default();
+            assert!(contract.create_post(PostType::RegularPost, "POST 1".to_string()).is_ok());
+            assert_eq!(contract.get_post_by_id(1).unwrap().media_uri, None);
+        }
+
+        #[ink::test]
+        fn test_create_post_with_invalid_media() {
+            let mut contract = Posts::default();
+            assert_eq!(
+                contract.create_post_with_media(
+                    PostType::RegularPost,
+                    "POST 1".to_string(),
+                    Some("".to_string()),
+                ),
+                Err(Error::InvalidMediaUri)
+            );
+        }
     }
 }